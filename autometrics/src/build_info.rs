@@ -0,0 +1,130 @@
+//! Emits a `build_info` gauge so that a function's other metrics can be
+//! correlated with the exact commit and version that produced them.
+//!
+//! This is what lets the hover links the `autometrics` macro generates
+//! answer "which deploy introduced this regression?" instead of just
+//! "this function is erroring".
+//!
+//! Every label is resolved at compile time from `Cargo.toml` and the
+//! `AUTOMETRICS_*` environment variables present during the build, via
+//! `env!`/`option_env!`. Setting the same `AUTOMETRICS_*` variable at runtime
+//! overrides the compiled-in value, which is useful when the real commit
+//! SHA is only known at deploy time (e.g. baked in by a container entrypoint).
+
+/// One of the repository hosts we know how to recognize from a
+/// `package.repository` URL.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RepositoryProvider {
+    GitHub,
+    GitLab,
+    BitBucket,
+    Unknown,
+}
+
+impl RepositoryProvider {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            RepositoryProvider::GitHub => "github",
+            RepositoryProvider::GitLab => "gitlab",
+            RepositoryProvider::BitBucket => "bitbucket",
+            RepositoryProvider::Unknown => "unknown",
+        }
+    }
+
+    /// Guess the provider from a `package.repository` URL such as
+    /// `https://github.com/autometrics-dev/autometrics-rs`.
+    pub(crate) fn from_repository_url(url: &str) -> Self {
+        if url.contains("github.com") {
+            RepositoryProvider::GitHub
+        } else if url.contains("gitlab.com") {
+            RepositoryProvider::GitLab
+        } else if url.contains("bitbucket.org") {
+            RepositoryProvider::BitBucket
+        } else {
+            RepositoryProvider::Unknown
+        }
+    }
+}
+
+/// The labels attached to the `build_info` gauge.
+///
+/// Compiled-in defaults come from `Cargo.toml` and `AUTOMETRICS_*` build-time
+/// environment variables; [`BuildInfo::resolve`] then lets a same-named
+/// runtime environment variable override each one, since the final commit
+/// SHA or branch is sometimes only known once the container starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub version: String,
+    pub commit: String,
+    pub branch: String,
+    pub service_name: String,
+    pub repository_url: String,
+    pub repository_provider: &'static str,
+}
+
+impl BuildInfo {
+    /// Resolve the final set of `build_info` labels, letting runtime
+    /// `AUTOMETRICS_*` environment variables override whatever was baked in
+    /// at compile time.
+    pub fn resolve() -> Self {
+        let compiled_version = env!("CARGO_PKG_VERSION");
+        let compiled_repository = env!("CARGO_PKG_REPOSITORY");
+        let compiled_commit = option_env!("AUTOMETRICS_COMMIT").unwrap_or("");
+        let compiled_branch = option_env!("AUTOMETRICS_BRANCH").unwrap_or("");
+        let compiled_service_name = option_env!("AUTOMETRICS_SERVICE_NAME").unwrap_or(env!("CARGO_PKG_NAME"));
+
+        let version = runtime_override("AUTOMETRICS_VERSION", compiled_version);
+        let commit = runtime_override("AUTOMETRICS_COMMIT", compiled_commit);
+        let branch = runtime_override("AUTOMETRICS_BRANCH", compiled_branch);
+        let service_name = runtime_override("AUTOMETRICS_SERVICE_NAME", compiled_service_name);
+        let repository_url = runtime_override("AUTOMETRICS_REPOSITORY_URL", compiled_repository);
+        let repository_provider = RepositoryProvider::from_repository_url(&repository_url).as_str();
+
+        Self {
+            version,
+            commit,
+            branch,
+            service_name,
+            repository_url,
+            repository_provider,
+        }
+    }
+}
+
+fn runtime_override(env_var: &str, compiled_in: &str) -> String {
+    std::env::var(env_var).unwrap_or_else(|_| compiled_in.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_repository_url_recognizes_the_major_hosts() {
+        assert_eq!(
+            RepositoryProvider::from_repository_url("https://github.com/autometrics-dev/autometrics-rs"),
+            RepositoryProvider::GitHub
+        );
+        assert_eq!(
+            RepositoryProvider::from_repository_url("https://gitlab.com/some/project"),
+            RepositoryProvider::GitLab
+        );
+        assert_eq!(
+            RepositoryProvider::from_repository_url("https://bitbucket.org/some/project"),
+            RepositoryProvider::BitBucket
+        );
+        assert_eq!(
+            RepositoryProvider::from_repository_url("https://example.com/some/project"),
+            RepositoryProvider::Unknown
+        );
+    }
+
+    #[test]
+    fn resolve_prefers_the_runtime_override_over_the_compiled_in_value() {
+        std::env::set_var("AUTOMETRICS_VERSION", "9.9.9-test-override");
+        let build_info = BuildInfo::resolve();
+        std::env::remove_var("AUTOMETRICS_VERSION");
+
+        assert_eq!(build_info.version, "9.9.9-test-override");
+    }
+}
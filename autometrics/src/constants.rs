@@ -0,0 +1,30 @@
+//! Names of the labels and metrics emitted by autometrics.
+//!
+//! Keeping these in one place means the `tracker`, `labels`, `objectives` and
+//! exporter modules can never disagree on what a label or metric is called.
+
+pub(crate) const COUNTER_NAME: &str = "function_calls_total";
+pub(crate) const HISTOGRAM_NAME: &str = "function_calls_duration_seconds";
+pub(crate) const GAUGE_NAME: &str = "function_calls_concurrent";
+pub(crate) const BUILD_INFO_NAME: &str = "build_info";
+
+pub(crate) const FUNCTION_KEY: &str = "function";
+pub(crate) const MODULE_KEY: &str = "module";
+pub(crate) const CALLER_KEY: &str = "caller";
+pub(crate) const RESULT_KEY: &str = "result";
+pub(crate) const OK_KEY: &str = "ok";
+pub(crate) const ERROR_KEY: &str = "error";
+
+pub(crate) const OBJECTIVE_NAME_KEY: &str = "objective_name";
+pub(crate) const OBJECTIVE_PERCENTILE_KEY: &str = "objective_percentile";
+pub(crate) const OBJECTIVE_LATENCY_THRESHOLD_KEY: &str = "objective_latency_threshold";
+
+pub(crate) const VERSION_KEY: &str = "version";
+pub(crate) const COMMIT_KEY: &str = "commit";
+pub(crate) const BRANCH_KEY: &str = "branch";
+// Prometheus label names must match `[a-zA-Z_][a-zA-Z0-9_]*`, so these use
+// underscores rather than the dotted OpenTelemetry resource attribute names
+// (`service.name`, etc.) they're derived from.
+pub(crate) const SERVICE_NAME_KEY: &str = "service_name";
+pub(crate) const REPOSITORY_URL_KEY: &str = "repository_url";
+pub(crate) const REPOSITORY_PROVIDER_KEY: &str = "repository_provider";
@@ -0,0 +1,35 @@
+//! Attach the active trace to a latency sample, so dashboards can jump
+//! straight from "this function is slow" to "here's the exact slow request".
+//!
+//! Gated behind the `exemplars` feature (and the
+//! [`exemplars`](crate::prometheus_exporter::AutometricsSettingsBuilder::exemplars)
+//! settings toggle, since exemplars are only useful when the scraper - and
+//! whatever renders the result, e.g. Grafana - both understand OpenMetrics).
+
+use opentelemetry::trace::TraceContextExt;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// The identifiers needed to link a metric sample back to the trace that
+/// produced it.
+pub(crate) struct TraceExemplar {
+    pub trace_id: String,
+    pub span_id: String,
+}
+
+/// Read the `trace_id`/`span_id` off the currently active `tracing` span, if
+/// any. Returns `None` when there's no active span, or the active span isn't
+/// sampled (and so has no real OpenTelemetry context attached).
+pub(crate) fn current_trace_exemplar() -> Option<TraceExemplar> {
+    let context = tracing::Span::current().context();
+    let span_ref = context.span();
+    let span_context = span_ref.span_context();
+
+    if !span_context.is_valid() || !span_context.is_sampled() {
+        return None;
+    }
+
+    Some(TraceExemplar {
+        trace_id: span_context.trace_id().to_string(),
+        span_id: span_context.span_id().to_string(),
+    })
+}
@@ -0,0 +1,163 @@
+//! Label sets attached to each of the metrics autometrics records.
+//!
+//! These are built once per instrumented call by the code the `autometrics`
+//! macro generates, and are shared by every exporter backend so that the
+//! same function call always produces the same labels no matter how the
+//! metrics end up leaving the process.
+
+use crate::constants::{
+    CALLER_KEY, ERROR_KEY, FUNCTION_KEY, MODULE_KEY, OBJECTIVE_LATENCY_THRESHOLD_KEY,
+    OBJECTIVE_NAME_KEY, OBJECTIVE_PERCENTILE_KEY, OK_KEY, RESULT_KEY,
+};
+use crate::objectives::Objective;
+
+/// The `objective_name` label value for a metric not scoped to any objective.
+///
+/// Always emitting the same `objective_*` label keys for a given metric
+/// (rather than omitting them) keeps every series under a metric name at the same label
+/// cardinality, which `with_label_values` requires; an empty `objective_name`
+/// simply never matches a rule's `objective_name="..."` selector.
+fn objective_name_pair(objective: Option<Objective>) -> (&'static str, String) {
+    (OBJECTIVE_NAME_KEY, objective.map(|o| o.name.to_string()).unwrap_or_default())
+}
+
+/// The `objective_percentile` label for the objective's success-rate target,
+/// formatted the same way [`objectives::generate_rules`](crate::objectives::generate_rules)
+/// formats it on the alerts it generates.
+fn objective_success_rate_percentile_pair(objective: Option<Objective>) -> (&'static str, String) {
+    (
+        OBJECTIVE_PERCENTILE_KEY,
+        objective
+            .and_then(|o| o.success_rate)
+            .map(|p| p.as_f64().to_string())
+            .unwrap_or_default(),
+    )
+}
+
+/// The `objective_percentile` label for the objective's latency target.
+fn objective_latency_percentile_pair(objective: Option<Objective>) -> (&'static str, String) {
+    (
+        OBJECTIVE_PERCENTILE_KEY,
+        objective
+            .and_then(|o| o.latency)
+            .map(|l| l.percentile.as_f64().to_string())
+            .unwrap_or_default(),
+    )
+}
+
+/// The `objective_latency_threshold` label for the objective's latency target.
+fn objective_latency_threshold_pair(objective: Option<Objective>) -> (&'static str, String) {
+    (
+        OBJECTIVE_LATENCY_THRESHOLD_KEY,
+        objective
+            .and_then(|o| o.latency)
+            .map(|l| l.threshold_seconds.to_string())
+            .unwrap_or_default(),
+    )
+}
+
+/// The result of a function call, as far as the generated metrics are concerned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Result {
+    Ok,
+    Error,
+}
+
+impl Result {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Result::Ok => OK_KEY,
+            Result::Error => ERROR_KEY,
+        }
+    }
+}
+
+/// Labels attached to the `function_calls_total` counter.
+#[doc(hidden)]
+pub struct CounterLabels {
+    pub function: &'static str,
+    pub module: &'static str,
+    pub caller: &'static str,
+    pub result: Option<Result>,
+    pub objective: Option<Objective>,
+}
+
+impl CounterLabels {
+    pub fn new(
+        function: &'static str,
+        module: &'static str,
+        caller: &'static str,
+        result: Option<Result>,
+        objective: Option<Objective>,
+    ) -> Self {
+        Self {
+            function,
+            module,
+            caller,
+            result,
+            objective,
+        }
+    }
+
+    /// The label key/value pairs, in the order they should be emitted.
+    ///
+    /// Always returns the same number of pairs, in the same order, so it can
+    /// be fed straight into `with_label_values` regardless of whether this
+    /// particular call has a `result` or an `objective`.
+    pub fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (FUNCTION_KEY, self.function.to_string()),
+            (MODULE_KEY, self.module.to_string()),
+            (CALLER_KEY, self.caller.to_string()),
+            (RESULT_KEY, self.result.map(|r| r.as_str().to_string()).unwrap_or_default()),
+            objective_name_pair(self.objective),
+            objective_success_rate_percentile_pair(self.objective),
+        ]
+    }
+}
+
+/// Labels attached to the `function_calls_duration_seconds` histogram.
+#[doc(hidden)]
+pub struct HistogramLabels {
+    pub function: &'static str,
+    pub module: &'static str,
+    pub objective: Option<Objective>,
+}
+
+impl HistogramLabels {
+    pub fn new(function: &'static str, module: &'static str, objective: Option<Objective>) -> Self {
+        Self {
+            function,
+            module,
+            objective,
+        }
+    }
+
+    /// The label key/value pairs, in the order they should be emitted. See
+    /// [`CounterLabels::to_pairs`] for why these are always the same shape.
+    pub fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (FUNCTION_KEY, self.function.to_string()),
+            (MODULE_KEY, self.module.to_string()),
+            objective_name_pair(self.objective),
+            objective_latency_percentile_pair(self.objective),
+            objective_latency_threshold_pair(self.objective),
+        ]
+    }
+}
+
+/// Labels attached to the `function_calls_concurrent` gauge.
+#[doc(hidden)]
+pub struct GaugeLabels {
+    pub function: &'static str,
+    pub module: &'static str,
+}
+
+impl GaugeLabels {
+    pub fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (FUNCTION_KEY, self.function.to_string()),
+            (MODULE_KEY, self.module.to_string()),
+        ]
+    }
+}
@@ -5,11 +5,19 @@
 #![cfg_attr(docsrs, doc(cfg_hide(doc)))]
 #![doc = include_str!("../README.md")]
 
+mod build_info;
 mod constants;
+#[cfg(feature = "exemplars")]
+mod exemplars;
 mod labels;
 pub mod objectives;
+#[cfg(feature = "otlp-exporter")]
+pub mod otlp_exporter;
 #[cfg(feature = "prometheus-exporter")]
 mod prometheus_exporter;
+pub mod queries;
+#[cfg(any(feature = "prometheus", feature = "prometheus-exporter"))]
+mod registry;
 mod task_local;
 mod tracker;
 
@@ -132,6 +140,16 @@ mod tracker;
 ///
 /// This will instrument all functions in the `impl` block, except for those that have the `skip_autometrics` attribute.
 ///
+/// ## Commit-level correlation
+///
+/// When the `prometheus-exporter` feature is enabled, every process also
+/// emits a `build_info` gauge carrying the version, commit and branch it was
+/// built from, and this crate exposes the join clause needed to correlate
+/// against it (see [`queries::BUILD_INFO_JOIN`]). Once `autometrics-macros`
+/// applies that join to a function's hover-link queries, you'll be able to
+/// break down its error rate or latency by the exact commit that was running
+/// when it happened - going from "this function regressed" to "here's the
+/// commit that did it".
 pub use autometrics_macros::autometrics;
 
 /// # Autometrics custom error labelling
@@ -180,6 +198,10 @@ pub use self::prometheus_exporter::*;
 
 /// We use the histogram buckets recommended by the OpenTelemetry specification
 /// https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/metrics/sdk.md#explicit-bucket-histogram-aggregation
+///
+/// This is only the default: pass `.histogram_buckets(...)` to
+/// [`AutometricsSettings::builder`](crate::prometheus_exporter::AutometricsSettings::builder)
+/// to override it.
 #[cfg(any(feature = "prometheus", feature = "prometheus-exporter"))]
 pub(crate) const HISTOGRAM_BUCKETS: [f64; 14] = [
     0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
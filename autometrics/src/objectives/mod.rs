@@ -0,0 +1,88 @@
+//! Declare Service-Level Objectives (SLOs) for your functions.
+//!
+//! ```rust
+//! use autometrics::objectives::{Objective, ObjectivePercentile};
+//!
+//! const API_SLO: Objective = Objective::new("api")
+//!     .success_rate(ObjectivePercentile::P99_9);
+//! ```
+//!
+//! Attach an [`Objective`] to a function with `#[autometrics(objective = ...)]`
+//! and the generated Prometheus query links will be scoped to it.
+//!
+//! Every function decorated this way also registers its [`Objective`] into
+//! [`OBJECTIVES`], so [`generate_rules`] can emit the matching Prometheus
+//! alerting rules without you having to list your SLOs twice.
+
+mod rules;
+
+pub use rules::{generate_rules, write_rules_file};
+
+/// Every [`Objective`] used anywhere in the binary via `#[autometrics(objective = ...)]`.
+///
+/// Populated by code the `autometrics` macro generates next to each
+/// `#[autometrics(objective = ...)]` call site; you shouldn't need to push
+/// into this yourself.
+#[linkme::distributed_slice]
+pub static OBJECTIVES: [Objective] = [..];
+
+/// The percentile of requests that must meet the objective.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum ObjectivePercentile {
+    P90,
+    P95,
+    P99,
+    P99_9,
+}
+
+impl ObjectivePercentile {
+    pub(crate) fn as_f64(&self) -> f64 {
+        match self {
+            ObjectivePercentile::P90 => 0.90,
+            ObjectivePercentile::P95 => 0.95,
+            ObjectivePercentile::P99 => 0.99,
+            ObjectivePercentile::P99_9 => 0.999,
+        }
+    }
+}
+
+/// A target latency threshold that some percentile of requests must meet.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ObjectiveLatency {
+    pub threshold_seconds: f64,
+    pub percentile: ObjectivePercentile,
+}
+
+/// A Service-Level Objective for one or more autometrics-instrumented functions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Objective {
+    pub name: &'static str,
+    pub success_rate: Option<ObjectivePercentile>,
+    pub latency: Option<ObjectiveLatency>,
+}
+
+impl Objective {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            success_rate: None,
+            latency: None,
+        }
+    }
+
+    /// Require that at least `percentile` of calls succeed.
+    pub const fn success_rate(mut self, percentile: ObjectivePercentile) -> Self {
+        self.success_rate = Some(percentile);
+        self
+    }
+
+    /// Require that at least `percentile` of calls complete within `threshold_seconds`.
+    pub const fn latency(mut self, threshold_seconds: f64, percentile: ObjectivePercentile) -> Self {
+        self.latency = Some(ObjectiveLatency {
+            threshold_seconds,
+            percentile,
+        });
+        self
+    }
+}
@@ -0,0 +1,176 @@
+//! Generate Prometheus alerting rules from the [`Objective`]s declared in
+//! this binary, instead of requiring users to hand-install (and hand-update)
+//! a separately-versioned rules file.
+//!
+//! The rules implement the multi-window, multi-burn-rate alerting strategy
+//! from the Google SRE workbook: a short and a long window must *both* be
+//! burning the error budget too fast before paging, which rules out both
+//! flapping on short blips and missing slow burns.
+
+use super::{Objective, ObjectivePercentile, OBJECTIVES};
+use crate::constants::{
+    OBJECTIVE_LATENCY_THRESHOLD_KEY, OBJECTIVE_NAME_KEY, OBJECTIVE_PERCENTILE_KEY,
+};
+
+/// `(short window, long window, burn rate multiplier, severity)`, one entry
+/// per alert, following the SRE workbook's recommended thresholds for a
+/// 30-day window.
+const BURN_RATE_WINDOWS: [(&str, &str, f64, &str); 4] = [
+    ("5m", "1h", 14.4, "critical"),
+    ("30m", "6h", 6.0, "critical"),
+    ("2h", "1d", 3.0, "warning"),
+    ("6h", "3d", 1.0, "warning"),
+];
+
+/// Build a ready-to-load Prometheus rules YAML file with a multi-window,
+/// multi-burn-rate alert for every [`Objective`] declared with
+/// `#[autometrics(objective = ...)]` anywhere in the binary.
+///
+/// The alert labels (`objective_name`, `objective_percentile`,
+/// `objective_latency_threshold`) match exactly what this crate attaches to
+/// the underlying metrics, so the alert expressions never drift from the
+/// labels the code actually emits.
+pub fn generate_rules() -> String {
+    let mut groups = Vec::new();
+    for objective in OBJECTIVES.iter() {
+        groups.push(success_rate_group(objective));
+        groups.push(latency_group(objective));
+    }
+    let groups: Vec<_> = groups.into_iter().flatten().collect();
+
+    let mut yaml = String::from("groups:\n");
+    for group in groups {
+        yaml.push_str(&group);
+    }
+    yaml
+}
+
+/// Write [`generate_rules`]'s output to `path`, overwriting it if it exists.
+///
+/// Meant to be called from a build script or a CI step so the checked-in
+/// rules file is regenerated - and so CI can fail a diff - whenever an
+/// `Objective` changes, rather than trusting someone to update it by hand.
+pub fn write_rules_file(path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+    std::fs::write(path, generate_rules())
+}
+
+fn success_rate_group(objective: &Objective) -> Option<String> {
+    let percentile = objective.success_rate?;
+    let target = percentile.as_f64();
+
+    let mut rules = String::new();
+    for (short, long, burn_rate, severity) in BURN_RATE_WINDOWS {
+        rules.push_str(&alert_rule(
+            &format!("{}HighErrorRate_{short}_{long}", objective.name),
+            severity,
+            &format!(
+                "(\n  sum(rate(function_calls_total{{objective_name=\"{name}\",result=\"error\"}}[{short}]))\n  /\n  sum(rate(function_calls_total{{objective_name=\"{name}\"}}[{short}]))\n) > {burn_rate} * (1 - {target})\nand\n(\n  sum(rate(function_calls_total{{objective_name=\"{name}\",result=\"error\"}}[{long}]))\n  /\n  sum(rate(function_calls_total{{objective_name=\"{name}\"}}[{long}]))\n) > {burn_rate} * (1 - {target})",
+                name = objective.name,
+            ),
+            objective,
+            percentile,
+            None,
+        ));
+    }
+
+    Some(format!(
+        "  - name: {name}_success_rate\n    rules:\n{rules}",
+        name = objective.name,
+    ))
+}
+
+fn latency_group(objective: &Objective) -> Option<String> {
+    let latency = objective.latency?;
+    let target = latency.percentile.as_f64();
+
+    let mut rules = String::new();
+    for (short, long, burn_rate, severity) in BURN_RATE_WINDOWS {
+        rules.push_str(&alert_rule(
+            &format!("{}HighLatency_{short}_{long}", objective.name),
+            severity,
+            &format!(
+                "(\n  1 - (\n    sum(rate(function_calls_duration_seconds_bucket{{objective_name=\"{name}\",le=\"{threshold}\"}}[{short}]))\n    /\n    sum(rate(function_calls_duration_seconds_count{{objective_name=\"{name}\"}}[{short}]))\n  )\n) > {burn_rate} * (1 - {target})\nand\n(\n  1 - (\n    sum(rate(function_calls_duration_seconds_bucket{{objective_name=\"{name}\",le=\"{threshold}\"}}[{long}]))\n    /\n    sum(rate(function_calls_duration_seconds_count{{objective_name=\"{name}\"}}[{long}]))\n  )\n) > {burn_rate} * (1 - {target})",
+                name = objective.name,
+                threshold = latency.threshold_seconds,
+            ),
+            objective,
+            latency.percentile,
+            Some(latency.threshold_seconds),
+        ));
+    }
+
+    Some(format!(
+        "  - name: {name}_latency\n    rules:\n{rules}",
+        name = objective.name,
+    ))
+}
+
+fn alert_rule(
+    alert_name: &str,
+    severity: &str,
+    expr: &str,
+    objective: &Objective,
+    percentile: ObjectivePercentile,
+    latency_threshold: Option<f64>,
+) -> String {
+    let mut labels = format!(
+        "        severity: {severity}\n        {OBJECTIVE_NAME_KEY}: \"{}\"\n        {OBJECTIVE_PERCENTILE_KEY}: \"{}\"\n",
+        objective.name,
+        percentile.as_f64(),
+    );
+    if let Some(threshold) = latency_threshold {
+        labels.push_str(&format!(
+            "        {OBJECTIVE_LATENCY_THRESHOLD_KEY}: \"{threshold}\"\n"
+        ));
+    }
+
+    format!(
+        "      - alert: {alert_name}\n        expr: |\n{indented_expr}\n        labels:\n{labels}",
+        indented_expr = indent(expr, 10),
+    )
+}
+
+fn indent(text: &str, spaces: usize) -> String {
+    let prefix = " ".repeat(spaces);
+    text.lines()
+        .map(|line| format!("{prefix}{line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Objective, ObjectivePercentile, OBJECTIVES};
+    use super::generate_rules;
+
+    // Registering into the same `OBJECTIVES` distributed slice the
+    // `autometrics` macro writes to at every `#[autometrics(objective = ...)]`
+    // call site, so this exercises `generate_rules` the same way it runs in
+    // a real binary.
+    #[linkme::distributed_slice(OBJECTIVES)]
+    static TEST_OBJECTIVE: Objective = Objective::new("rules_test_slo")
+        .success_rate(ObjectivePercentile::P99)
+        .latency(0.5, ObjectivePercentile::P95);
+
+    #[test]
+    fn generate_rules_emits_a_group_per_objective_kind() {
+        let yaml = generate_rules();
+        assert!(yaml.contains("rules_test_slo_success_rate"));
+        assert!(yaml.contains("rules_test_slo_latency"));
+    }
+
+    #[test]
+    fn generate_rules_keys_alerts_on_the_crate_s_own_objective_labels() {
+        let yaml = generate_rules();
+        assert!(yaml.contains("objective_name: \"rules_test_slo\""));
+        assert!(yaml.contains("objective_percentile: \"0.99\""));
+        assert!(yaml.contains("objective_latency_threshold: \"0.5\""));
+    }
+
+    #[test]
+    fn generate_rules_latency_expr_measures_the_exceeding_fraction() {
+        let yaml = generate_rules();
+        assert!(yaml.contains("function_calls_duration_seconds_count{objective_name=\"rules_test_slo\"}"));
+        assert!(!yaml.contains("histogram_quantile"));
+    }
+}
@@ -0,0 +1,193 @@
+//! A push-based exporter that periodically sends autometrics' metrics to an
+//! OpenTelemetry collector over OTLP.
+//!
+//! This is the counterpart to [`prometheus_exporter`](crate::prometheus_exporter)
+//! for environments where nothing can scrape a `/metrics` endpoint, such as
+//! serverless functions or short-lived batch jobs. `tracker` writes every
+//! function call into both backends' instruments side by side, using the
+//! same label set, so the generated PromQL queries work unmodified no matter
+//! which transport you choose.
+//!
+//! ```rust,no_run
+//! use autometrics::otlp_exporter::{init_push_exporter, OtlpTransport};
+//! use std::time::Duration;
+//!
+//! init_push_exporter()
+//!     .with_endpoint("http://localhost:4317")
+//!     .with_interval(Duration::from_secs(10))
+//!     .with_transport(OtlpTransport::Grpc)
+//!     .build()
+//!     .expect("failed to start the OTLP push exporter");
+//! ```
+
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+use opentelemetry::metrics::{Counter, Histogram, MetricsError, UpDownCounter};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{metrics::PeriodicReader, runtime};
+
+use crate::constants::{COUNTER_NAME, GAUGE_NAME, HISTOGRAM_NAME};
+use crate::HISTOGRAM_BUCKETS;
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:4317";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The instruments `tracker` records into once [`PushExporterBuilder::build`]
+/// has run. Kept behind a `OnceCell` (rather than in self-initializing `Lazy`
+/// statics like the Prometheus instruments in `registry`) because
+/// constructing them requires a configured collector endpoint and transport.
+struct Instruments {
+    counter: Counter<u64>,
+    histogram: Histogram<f64>,
+    gauge: UpDownCounter<i64>,
+}
+
+static INSTRUMENTS: OnceCell<Instruments> = OnceCell::new();
+
+/// Record a function call result into the `function_calls_total` OTLP counter.
+///
+/// No-op until [`PushExporterBuilder::build`] has run, so this is safe to call
+/// unconditionally from `tracker` regardless of whether the push exporter is
+/// actually in use.
+pub(crate) fn record_counter(attributes: &[KeyValue]) {
+    if let Some(instruments) = INSTRUMENTS.get() {
+        instruments.counter.add(1, attributes);
+    }
+}
+
+/// Record a function call duration into the `function_calls_duration_seconds` OTLP histogram.
+pub(crate) fn record_histogram(duration_seconds: f64, attributes: &[KeyValue]) {
+    if let Some(instruments) = INSTRUMENTS.get() {
+        instruments.histogram.record(duration_seconds, attributes);
+    }
+}
+
+/// Adjust the `function_calls_concurrent` OTLP up/down counter.
+pub(crate) fn record_gauge(delta: i64, attributes: &[KeyValue]) {
+    if let Some(instruments) = INSTRUMENTS.get() {
+        instruments.gauge.add(delta, attributes);
+    }
+}
+
+/// Convert the label pairs `tracker` already builds for the Prometheus
+/// backend into OTel attributes, so both backends stay in lockstep without
+/// `tracker` needing to know either exporter's attribute type.
+pub(crate) fn key_values(pairs: &[(&'static str, String)]) -> Vec<KeyValue> {
+    pairs
+        .iter()
+        .map(|(key, value)| KeyValue::new(*key, value.clone()))
+        .collect()
+}
+
+/// The wire protocol used to talk to the OpenTelemetry collector.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OtlpTransport {
+    /// OTLP over gRPC (the collector's default port, `4317`).
+    Grpc,
+    /// OTLP over HTTP with protobuf-encoded bodies (the collector's default port, `4318`).
+    HttpBinary,
+}
+
+/// Builder for the OTLP push exporter.
+///
+/// Created with [`init_push_exporter`]; mirrors the builder pattern used by
+/// [`AutometricsSettings::builder`](crate::prometheus_exporter::AutometricsSettings::builder).
+pub struct PushExporterBuilder {
+    endpoint: String,
+    interval: Duration,
+    transport: OtlpTransport,
+}
+
+impl PushExporterBuilder {
+    /// The OTLP collector endpoint to push metrics to, e.g. `http://localhost:4317`.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    /// How often to push the current metrics to the collector.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Whether to push over gRPC or HTTP/protobuf.
+    pub fn with_transport(mut self, transport: OtlpTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Build the meter provider and start the periodic push task.
+    ///
+    /// Registers the same three autometrics instruments
+    /// (`function_calls_total`, `function_calls_duration_seconds`,
+    /// `function_calls_concurrent`) that [`prometheus_exporter`](crate::prometheus_exporter)
+    /// exposes for scraping, so dashboards and alerts don't need to know
+    /// which transport is in use.
+    pub fn build(self) -> Result<(), MetricsError> {
+        let exporter = match self.transport {
+            OtlpTransport::Grpc => opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&self.endpoint)
+                .build_metrics_exporter(
+                    Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+                    Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                )?,
+            OtlpTransport::HttpBinary => opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(&self.endpoint)
+                .build_metrics_exporter(
+                    Box::new(opentelemetry_sdk::metrics::reader::DefaultTemporalitySelector::new()),
+                    Box::new(opentelemetry_sdk::metrics::reader::DefaultAggregationSelector::new()),
+                )?,
+        };
+
+        let reader = PeriodicReader::builder(exporter, runtime::Tokio)
+            .with_interval(self.interval)
+            .build();
+
+        let provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+            .with_reader(reader)
+            .build();
+
+        let meter = provider.meter("autometrics");
+        // Instruments share autometrics' metric names so queries generated by
+        // the `autometrics` macro return identical results regardless of
+        // whether they're served via scrape or push. `tracker` records into
+        // these same instruments through `record_counter`/`record_histogram`/
+        // `record_gauge`, so a running push exporter actually receives data
+        // instead of only ever reporting empty series.
+        let counter = meter.u64_counter(COUNTER_NAME).init();
+        let histogram = meter
+            .f64_histogram(HISTOGRAM_NAME)
+            .with_boundaries(HISTOGRAM_BUCKETS.to_vec())
+            .init();
+        let gauge = meter.i64_up_down_counter(GAUGE_NAME).init();
+
+        INSTRUMENTS
+            .set(Instruments {
+                counter,
+                histogram,
+                gauge,
+            })
+            .map_err(|_| MetricsError::Other("the OTLP push exporter has already been started".into()))?;
+
+        opentelemetry::global::set_meter_provider(provider);
+        Ok(())
+    }
+}
+
+/// Start configuring a push-based OTLP exporter for autometrics' metrics.
+///
+/// Defaults to pushing to `http://localhost:4317` over gRPC every 60 seconds;
+/// override any of these with the builder methods before calling
+/// [`PushExporterBuilder::build`].
+pub fn init_push_exporter() -> PushExporterBuilder {
+    PushExporterBuilder {
+        endpoint: DEFAULT_ENDPOINT.to_string(),
+        interval: DEFAULT_INTERVAL,
+        transport: OtlpTransport::Grpc,
+    }
+}
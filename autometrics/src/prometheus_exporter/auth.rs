@@ -0,0 +1,101 @@
+//! Optional bearer-token guard for the `/metrics` endpoint.
+//!
+//! By default the standalone exporter (and anyone wiring
+//! [`encode_metrics`](super::encode_metrics) into their own server) serves
+//! metrics with no access control. Setting
+//! [`AutometricsSettings::builder().metrics_authorization(...)`](super::AutometricsSettings)
+//! (or the `AUTOMETRICS_METRICS_AUTHORIZATION` environment variable) requires
+//! scrapers to send a `Bearer` `Authorization` header carrying that token
+//! instead - configure just the token itself, not the `Bearer ` scheme.
+
+const BEARER_SCHEME_PREFIX: &str = "Bearer ";
+
+/// The outcome of checking a scrape request's `Authorization` header against
+/// the configured token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AuthorizationResult {
+    /// No token is configured, or the request's header matched it.
+    Allowed,
+    /// A token is configured and the request's header didn't match; the
+    /// caller should respond `401 Unauthorized`.
+    Denied,
+}
+
+/// Check a scrape request's `Authorization` header value (if any) against the
+/// configured `expected_token` (if any).
+///
+/// `expected_token` is the bare token, without the `Bearer ` scheme; the
+/// header is expected to carry it as `Authorization: Bearer <token>`, per
+/// RFC 6750. The comparison runs in constant time with respect to the
+/// token's contents, so a scraper can't recover it byte-by-byte by timing
+/// failed requests.
+///
+/// This is what [`AutometricsExporter`](super::AutometricsExporter) uses
+/// internally, and is also exposed so it can be dropped straight into an
+/// axum/actix middleware or extractor guarding a hand-rolled `/metrics` route.
+pub fn check_authorization(expected_token: Option<&str>, authorization_header: Option<&str>) -> AuthorizationResult {
+    match expected_token {
+        None => AuthorizationResult::Allowed,
+        Some(expected) => {
+            let presented = authorization_header.and_then(|header| header.strip_prefix(BEARER_SCHEME_PREFIX));
+            match presented {
+                Some(presented) if constant_time_eq(presented.as_bytes(), expected.as_bytes()) => {
+                    AuthorizationResult::Allowed
+                }
+                _ => AuthorizationResult::Denied,
+            }
+        }
+    }
+}
+
+/// Compare two byte strings for equality in time that depends only on their
+/// lengths, not their contents, so a failed comparison doesn't leak how many
+/// leading bytes of the token a guess got right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_when_no_token_is_configured() {
+        assert_eq!(check_authorization(None, None), AuthorizationResult::Allowed);
+        assert_eq!(
+            check_authorization(None, Some("Bearer whatever")),
+            AuthorizationResult::Allowed
+        );
+    }
+
+    #[test]
+    fn allowed_when_the_header_carries_a_matching_bearer_token() {
+        assert_eq!(
+            check_authorization(Some("secret"), Some("Bearer secret")),
+            AuthorizationResult::Allowed
+        );
+    }
+
+    #[test]
+    fn denied_when_the_header_is_missing_mismatched_or_missing_the_bearer_scheme() {
+        assert_eq!(check_authorization(Some("secret"), None), AuthorizationResult::Denied);
+        assert_eq!(
+            check_authorization(Some("secret"), Some("Bearer wrong")),
+            AuthorizationResult::Denied
+        );
+        assert_eq!(
+            check_authorization(Some("secret"), Some("secret")),
+            AuthorizationResult::Denied
+        );
+    }
+
+    #[test]
+    fn constant_time_eq_matches_string_equality() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"wrong!"));
+        assert!(!constant_time_eq(b"secret", b"secre"));
+    }
+}
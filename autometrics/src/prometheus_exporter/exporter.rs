@@ -0,0 +1,106 @@
+//! A standalone HTTP server that serves `/metrics`.
+//!
+//! Most users embed [`encode_metrics`](super::encode_metrics) into a route on
+//! a server they already run; [`AutometricsExporter`] is for the cases where
+//! there isn't one, e.g. a background worker that only needs to expose this
+//! one endpoint.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use http::{Response, StatusCode};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Server};
+
+use super::auth::{check_authorization, AuthorizationResult};
+use super::settings::AutometricsSettings;
+
+/// A standalone server exposing `/metrics` in the Prometheus text format.
+///
+/// Construct with [`AutometricsExporter::builder`].
+pub struct AutometricsExporter {
+    addr: SocketAddr,
+    settings: AutometricsSettings,
+}
+
+impl AutometricsExporter {
+    pub fn builder() -> AutometricsExporterBuilder {
+        AutometricsExporterBuilder::default()
+    }
+
+    /// Start the server. Resolves once the server has shut down.
+    pub async fn start(self) -> Result<(), hyper::Error> {
+        let settings = self.settings;
+        let make_svc = make_service_fn(move |_conn| {
+            let settings = settings.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let settings = settings.clone();
+                    async move { Ok::<_, Infallible>(handle_request(req, &settings)) }
+                }))
+            }
+        });
+
+        Server::bind(&self.addr).serve(make_svc).await
+    }
+}
+
+fn handle_request(req: Request<Body>, settings: &AutometricsSettings) -> Response<Body> {
+    let authorization_header = req
+        .headers()
+        .get(http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+
+    match check_authorization(settings.metrics_authorization.as_deref(), authorization_header) {
+        AuthorizationResult::Denied => Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .expect("building a 401 response should never fail"),
+        AuthorizationResult::Allowed => {
+            let accept_header = req
+                .headers()
+                .get(http::header::ACCEPT)
+                .and_then(|value| value.to_str().ok());
+            let (body, content_type) = super::encode_metrics_for_accept(accept_header);
+
+            Response::builder()
+                .header(http::header::CONTENT_TYPE, content_type)
+                .body(Body::from(body))
+                .expect("building a 200 response should never fail")
+        }
+    }
+}
+
+/// Builder for [`AutometricsExporter`].
+#[derive(Default)]
+pub struct AutometricsExporterBuilder {
+    addr: Option<SocketAddr>,
+    settings: Option<AutometricsSettings>,
+}
+
+impl AutometricsExporterBuilder {
+    /// The address to listen on. Defaults to `0.0.0.0:9464`, the OpenTelemetry
+    /// convention for a Prometheus exporter.
+    pub fn with_address(mut self, addr: SocketAddr) -> Self {
+        self.addr = Some(addr);
+        self
+    }
+
+    /// Use the given [`AutometricsSettings`] (e.g. to require
+    /// [`metrics_authorization`](AutometricsSettings::builder)).
+    pub fn with_settings(mut self, settings: AutometricsSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    pub fn build(self) -> AutometricsExporter {
+        AutometricsExporter {
+            addr: self
+                .addr
+                .unwrap_or_else(|| SocketAddr::from(([0, 0, 0, 0], 9464))),
+            settings: self
+                .settings
+                .unwrap_or_else(|| AutometricsSettings::builder().build()),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! A pull-based exporter that serves metrics in the Prometheus text format,
+//! negotiating up to OpenMetrics when a scrape request asks for it.
+//!
+//! Call [`init_prometheus_exporter`] once at startup, then serve
+//! [`encode_metrics_for_accept`]'s output (or plain [`encode_metrics`], if you
+//! don't want to content-negotiate) from whatever HTTP server you're already
+//! running - [`AutometricsExporter::start`] does this for you if you'd rather
+//! spin up a standalone one.
+
+mod auth;
+mod exporter;
+mod settings;
+
+pub use auth::{check_authorization, AuthorizationResult};
+pub use exporter::{AutometricsExporter, AutometricsExporterBuilder};
+pub use settings::{AutometricsSettings, AutometricsSettingsBuilder};
+
+use crate::registry::HISTOGRAM_BUCKETS_OVERRIDE;
+#[cfg(feature = "exemplars")]
+pub(crate) use settings::exemplars_enabled;
+
+use once_cell::sync::Lazy;
+use prometheus::{Encoder, IntGaugeVec, TextEncoder};
+
+use crate::build_info::BuildInfo;
+use crate::constants::{
+    BRANCH_KEY, COMMIT_KEY, REPOSITORY_PROVIDER_KEY, REPOSITORY_URL_KEY, SERVICE_NAME_KEY,
+    VERSION_KEY,
+};
+use crate::registry::REGISTRY;
+
+static BUILD_INFO: Lazy<IntGaugeVec> = Lazy::new(|| {
+    let gauge = IntGaugeVec::new(
+        prometheus::Opts::new(
+            crate::constants::BUILD_INFO_NAME,
+            "Always 1; labels identify the running build for commit-level correlation",
+        ),
+        &[
+            VERSION_KEY,
+            COMMIT_KEY,
+            BRANCH_KEY,
+            SERVICE_NAME_KEY,
+            REPOSITORY_URL_KEY,
+            REPOSITORY_PROVIDER_KEY,
+        ],
+    )
+    .expect("creating the build_info gauge should never fail");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("registering the build_info gauge should never fail");
+    gauge
+});
+
+/// Set the `build_info` gauge to `1`, labeled with the version/commit/branch
+/// resolved by [`BuildInfo::resolve`]. Called once by [`init_prometheus_exporter`].
+fn record_build_info() {
+    let build_info = BuildInfo::resolve();
+    BUILD_INFO
+        .with_label_values(&[
+            &build_info.version,
+            &build_info.commit,
+            &build_info.branch,
+            &build_info.service_name,
+            &build_info.repository_url,
+            build_info.repository_provider,
+        ])
+        .set(1);
+}
+
+/// Register all of the autometrics metrics collectors against the global
+/// registry, using the default [`AutometricsSettings`].
+pub fn init_prometheus_exporter() {
+    init_prometheus_exporter_with_settings(AutometricsSettings::default())
+}
+
+/// Register all of the autometrics metrics collectors against the global
+/// registry, using the given [`AutometricsSettings`] (e.g. to require
+/// [`metrics_authorization`](AutometricsSettingsBuilder::metrics_authorization)
+/// on the `/metrics` endpoint, or to override the latency
+/// [`histogram_buckets`](AutometricsSettingsBuilder::histogram_buckets)).
+///
+/// Must be called before any autometrics-instrumented function runs, since
+/// the duration histogram's buckets are fixed the first time it's created.
+pub fn init_prometheus_exporter_with_settings(settings: AutometricsSettings) {
+    if let Some(buckets) = settings.histogram_buckets {
+        // Ignore the error: if this is already set, an earlier call already
+        // won the race and its buckets are what the histogram was built with.
+        let _ = HISTOGRAM_BUCKETS_OVERRIDE.set(buckets);
+    }
+
+    #[cfg(feature = "exemplars")]
+    let _ = settings::EXEMPLARS_ENABLED.set(settings.exemplars);
+
+    crate::registry::counter();
+    crate::registry::histogram();
+    crate::registry::gauge();
+    Lazy::force(&BUILD_INFO);
+    record_build_info();
+}
+
+/// The `Content-Type` of [`encode_metrics`]'s output: the legacy Prometheus
+/// text exposition format. Prometheus always accepts this.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The `Content-Type` of [`encode_openmetrics`]'s output.
+pub const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Render the current state of all autometrics metrics in the legacy
+/// Prometheus text exposition format, suitable for returning from a
+/// `/metrics` handler with a `Content-Type` of [`PROMETHEUS_CONTENT_TYPE`].
+///
+/// When the `exemplars` feature and settings toggle are both on, samples
+/// recorded with [`Histogram::observe_with_exemplar`](prometheus::Histogram::observe_with_exemplar)
+/// carry a trailing `# {trace_id="...",span_id="..."}` comment in the
+/// OpenMetrics exemplar syntax - but most Prometheus servers only parse that
+/// as an exemplar when the response is actually served as OpenMetrics (see
+/// [`encode_openmetrics`]), not when it's just a comment in the legacy
+/// format. Prefer [`encode_metrics_for_accept`] so exemplars reach Prometheus
+/// whenever the scraper asks for them.
+pub fn encode_metrics() -> std::string::String {
+    let metric_families = REGISTRY.gather();
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&metric_families, &mut buffer)
+        .expect("encoding the metrics as Prometheus text should never fail");
+    std::string::String::from_utf8(buffer).expect("Prometheus text encoding is always valid UTF-8")
+}
+
+/// Render the current state of all autometrics metrics as OpenMetrics text,
+/// suitable for returning from a `/metrics` handler with a `Content-Type` of
+/// [`OPENMETRICS_CONTENT_TYPE`].
+///
+/// `prometheus` doesn't ship a dedicated OpenMetrics encoder, but its text
+/// encoder's output - including the `# {trace_id="...",...}` exemplar
+/// comments [`encode_metrics`] mentions - is already valid OpenMetrics text
+/// apart from the trailing `# EOF` marker the spec requires, so this just
+/// appends it.
+pub fn encode_openmetrics() -> std::string::String {
+    let mut text = encode_metrics();
+    text.push_str("# EOF\n");
+    text
+}
+
+/// Render the current state of all autometrics metrics, negotiating the
+/// format and `Content-Type` from a scrape request's `Accept` header:
+/// [`encode_openmetrics`]/[`OPENMETRICS_CONTENT_TYPE`] when the header
+/// requests `application/openmetrics-text`, [`encode_metrics`]/
+/// [`PROMETHEUS_CONTENT_TYPE`] otherwise. [`AutometricsExporter`] uses this
+/// internally.
+pub fn encode_metrics_for_accept(accept_header: Option<&str>) -> (std::string::String, &'static str) {
+    if accept_header.is_some_and(|accept| accept.contains("application/openmetrics-text")) {
+        (encode_openmetrics(), OPENMETRICS_CONTENT_TYPE)
+    } else {
+        (encode_metrics(), PROMETHEUS_CONTENT_TYPE)
+    }
+}
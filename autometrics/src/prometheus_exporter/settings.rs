@@ -0,0 +1,92 @@
+//! Configuration for the Prometheus exporter.
+
+use once_cell::sync::OnceCell;
+
+/// Whether exemplars should be attached to observed latency samples. Set
+/// once, from [`init_prometheus_exporter_with_settings`](super::init_prometheus_exporter_with_settings).
+#[cfg(feature = "exemplars")]
+pub(crate) static EXEMPLARS_ENABLED: OnceCell<bool> = OnceCell::new();
+
+#[cfg(feature = "exemplars")]
+pub(crate) fn exemplars_enabled() -> bool {
+    EXEMPLARS_ENABLED.get().copied().unwrap_or(false)
+}
+
+/// Settings for the autometrics Prometheus integration.
+///
+/// Construct one with [`AutometricsSettings::builder`] and pass it to
+/// [`init_prometheus_exporter_with_settings`](super::init_prometheus_exporter_with_settings).
+#[derive(Clone, Debug, Default)]
+pub struct AutometricsSettings {
+    pub(crate) metrics_authorization: Option<String>,
+    pub(crate) histogram_buckets: Option<Vec<f64>>,
+    pub(crate) exemplars: bool,
+}
+
+impl AutometricsSettings {
+    /// Start building a new set of settings.
+    pub fn builder() -> AutometricsSettingsBuilder {
+        AutometricsSettingsBuilder::default()
+    }
+}
+
+/// Builder for [`AutometricsSettings`].
+#[derive(Default)]
+pub struct AutometricsSettingsBuilder {
+    metrics_authorization: Option<String>,
+    histogram_buckets: Option<Vec<f64>>,
+    exemplars: bool,
+}
+
+impl AutometricsSettingsBuilder {
+    /// Require the given bearer token on any request to the `/metrics`
+    /// endpoint, returning `401 Unauthorized` otherwise.
+    ///
+    /// Pass just the token, not the `Bearer ` scheme - requests must then
+    /// send it as `Authorization: Bearer <token>`, per RFC 6750.
+    ///
+    /// If not set, this falls back to the `AUTOMETRICS_METRICS_AUTHORIZATION`
+    /// environment variable, and if that's unset too, the endpoint is left
+    /// unauthenticated.
+    pub fn metrics_authorization(mut self, token: impl Into<String>) -> Self {
+        self.metrics_authorization = Some(token.into());
+        self
+    }
+
+    /// Override the bucket boundaries (in seconds) used by the
+    /// `function_calls_duration_seconds` histogram.
+    ///
+    /// Defaults to the 14 buckets recommended by the OpenTelemetry
+    /// specification, which may not fit services whose latencies cluster in
+    /// microseconds or span many seconds. Must be set before the first call
+    /// to [`init_prometheus_exporter_with_settings`](super::init_prometheus_exporter_with_settings);
+    /// the histogram is created once and its buckets can't change afterwards.
+    pub fn histogram_buckets(mut self, buckets: impl Into<Vec<f64>>) -> Self {
+        self.histogram_buckets = Some(buckets.into());
+        self
+    }
+
+    /// Attach the active trace's `trace_id` (and `span_id`) as an exemplar on
+    /// each observed latency sample, so Grafana/Prometheus can render a
+    /// clickable link from the histogram straight to the originating trace.
+    ///
+    /// Requires the `exemplars` feature. Has no effect when there's no active
+    /// `tracing`/OpenTelemetry span - the sample is just recorded without one.
+    #[cfg(feature = "exemplars")]
+    pub fn exemplars(mut self, enabled: bool) -> Self {
+        self.exemplars = enabled;
+        self
+    }
+
+    pub fn build(self) -> AutometricsSettings {
+        let metrics_authorization = self
+            .metrics_authorization
+            .or_else(|| std::env::var("AUTOMETRICS_METRICS_AUTHORIZATION").ok());
+
+        AutometricsSettings {
+            metrics_authorization,
+            histogram_buckets: self.histogram_buckets,
+            exemplars: self.exemplars,
+        }
+    }
+}
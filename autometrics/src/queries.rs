@@ -0,0 +1,43 @@
+//! Building blocks for the PromQL the `autometrics` macro embeds in its
+//! generated doc comment links.
+//!
+//! This crate only owns the label *names*; the actual query strings are
+//! assembled by `autometrics-macros` at compile time, using these constants
+//! so the two crates can never disagree on how to join against `build_info`.
+//! [`with_build_info_join`] itself has no caller inside this crate - it's
+//! public so `autometrics-macros` can depend on it instead of re-deriving
+//! the join clause, not because anything here invokes it.
+//!
+//! That means the commit-level-correlation join is only live once
+//! `autometrics-macros` actually calls [`with_build_info_join`] when it
+//! builds a function's hover-link queries - this crate can't do that
+//! wiring itself, since the macro's query-string assembly lives in that
+//! other crate, not here.
+
+/// Appended to a query so that its result can be broken
+/// down by the version/commit that produced it, e.g.:
+///
+/// ```text
+/// sum(rate(function_calls_total{function="my_fn"}[5m])) * on (instance, job) group_left(version, commit) build_info
+/// ```
+///
+/// `instance` and `job` are the labels Prometheus itself attaches to every
+/// scrape target, which is what lets this join line up a function's metrics
+/// with the `build_info` series emitted by the same process.
+pub const BUILD_INFO_JOIN: &str = "* on (instance, job) group_left(version, commit) build_info";
+
+/// Append the [`BUILD_INFO_JOIN`] clause to a PromQL query.
+pub fn with_build_info_join(query: &str) -> String {
+    format!("{query} {BUILD_INFO_JOIN}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_build_info_join_appends_the_join_clause() {
+        let query = "sum(rate(function_calls_total{function=\"my_fn\"}[5m]))";
+        assert_eq!(with_build_info_join(query), format!("{query} {BUILD_INFO_JOIN}"));
+    }
+}
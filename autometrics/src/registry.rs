@@ -0,0 +1,98 @@
+//! The process-global Prometheus registry and the three core instruments
+//! (`function_calls_total`, `function_calls_duration_seconds`,
+//! `function_calls_concurrent`) that `tracker` records into.
+//!
+//! Split out from [`prometheus_exporter`](crate::prometheus_exporter) so that
+//! the `prometheus` feature doesn't have to pull in `prometheus-exporter`'s
+//! HTTP server, auth guard and `build_info` gauge. The registry and the
+//! collectors are crate-private: nothing here exposes them for registration
+//! with another [`Registry`].
+
+use once_cell::sync::{Lazy, OnceCell};
+use prometheus::{HistogramVec, IntCounterVec, IntGaugeVec, Registry};
+
+use crate::constants::{
+    CALLER_KEY, FUNCTION_KEY, MODULE_KEY, OBJECTIVE_LATENCY_THRESHOLD_KEY, OBJECTIVE_NAME_KEY,
+    OBJECTIVE_PERCENTILE_KEY, RESULT_KEY,
+};
+use crate::HISTOGRAM_BUCKETS;
+
+pub(crate) static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// The user's override of the duration histogram's buckets, if any.
+///
+/// Set once, from
+/// [`init_prometheus_exporter_with_settings`](crate::prometheus_exporter::init_prometheus_exporter_with_settings),
+/// before the histogram is first constructed. Read here instead of the
+/// hardcoded [`HISTOGRAM_BUCKETS`] default whenever the user has overridden
+/// them.
+pub(crate) static HISTOGRAM_BUCKETS_OVERRIDE: OnceCell<Vec<f64>> = OnceCell::new();
+
+static COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = IntCounterVec::new(
+        prometheus::Opts::new(crate::constants::COUNTER_NAME, "Autometrics function call counter"),
+        &[
+            FUNCTION_KEY,
+            MODULE_KEY,
+            CALLER_KEY,
+            RESULT_KEY,
+            OBJECTIVE_NAME_KEY,
+            OBJECTIVE_PERCENTILE_KEY,
+        ],
+    )
+    .expect("creating the function_calls_total counter should never fail");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("registering the function_calls_total counter should never fail");
+    counter
+});
+
+static HISTOGRAM: Lazy<HistogramVec> = Lazy::new(|| {
+    let buckets = HISTOGRAM_BUCKETS_OVERRIDE
+        .get()
+        .cloned()
+        .unwrap_or_else(|| HISTOGRAM_BUCKETS.to_vec());
+    let histogram = HistogramVec::new(
+        prometheus::HistogramOpts::new(
+            crate::constants::HISTOGRAM_NAME,
+            "Autometrics function call duration",
+        )
+        .buckets(buckets),
+        &[
+            FUNCTION_KEY,
+            MODULE_KEY,
+            OBJECTIVE_NAME_KEY,
+            OBJECTIVE_PERCENTILE_KEY,
+            OBJECTIVE_LATENCY_THRESHOLD_KEY,
+        ],
+    )
+    .expect("creating the function_calls_duration_seconds histogram should never fail");
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("registering the function_calls_duration_seconds histogram should never fail");
+    histogram
+});
+
+static GAUGE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    let gauge = IntGaugeVec::new(
+        prometheus::Opts::new(crate::constants::GAUGE_NAME, "Autometrics function concurrency gauge"),
+        &[FUNCTION_KEY, MODULE_KEY],
+    )
+    .expect("creating the function_calls_concurrent gauge should never fail");
+    REGISTRY
+        .register(Box::new(gauge.clone()))
+        .expect("registering the function_calls_concurrent gauge should never fail");
+    gauge
+});
+
+pub(crate) fn counter() -> &'static IntCounterVec {
+    &COUNTER
+}
+
+pub(crate) fn histogram() -> &'static HistogramVec {
+    &HISTOGRAM
+}
+
+pub(crate) fn gauge() -> &'static IntGaugeVec {
+    &GAUGE
+}
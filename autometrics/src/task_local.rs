@@ -0,0 +1,34 @@
+//! A minimal, `std`-only stand-in for `tokio::task_local!`.
+//!
+//! We cannot use the `tokio` macro directly because it panics if the value
+//! hasn't been set, whereas we want callers of [`CALLER`](crate::__private::CALLER)
+//! to simply get back the empty string.
+
+use std::cell::RefCell;
+
+pub struct LocalKey<T: 'static> {
+    pub(crate) inner: &'static std::thread::LocalKey<RefCell<Option<T>>>,
+}
+
+impl<T: Copy + 'static> LocalKey<T> {
+    /// Get the current value, or `T::default()` if none has been set.
+    pub fn get(&'static self) -> T
+    where
+        T: Default,
+    {
+        self.inner
+            .with(|cell| cell.borrow().unwrap_or_default())
+    }
+
+    /// Run `f` with `value` set for the duration of the call, restoring the
+    /// previous value afterwards.
+    pub fn scope<F, R>(&'static self, value: T, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let previous = self.inner.with(|cell| cell.replace(Some(value)));
+        let result = f();
+        self.inner.with(|cell| *cell.borrow_mut() = previous);
+        result
+    }
+}
@@ -0,0 +1,96 @@
+//! Records the counter, histogram and gauge that back every instrumented
+//! function call.
+//!
+//! The `autometrics` macro calls into [`AutometricsTracker`] (via the
+//! [`TrackMetrics`] trait) at the start and end of a function call. Which
+//! concrete tracker is used depends on which backend feature is enabled;
+//! today that's the `prometheus`/`prometheus-exporter` backend.
+
+use crate::labels::{CounterLabels, GaugeLabels, HistogramLabels};
+use std::time::Instant;
+
+/// Implemented once per metrics backend (Prometheus, OpenTelemetry, ...).
+///
+/// The autometrics macro only ever talks to this trait, so adding a new
+/// backend never requires touching the generated code.
+pub trait TrackMetrics {
+    /// Called when an instrumented function is entered.
+    fn start(gauge_labels: Option<&GaugeLabels>) -> Self;
+
+    /// Called when an instrumented function returns, recording its duration
+    /// and result.
+    fn finish(self, counter_labels: &CounterLabels, histogram_labels: &HistogramLabels);
+}
+
+/// The tracker used when the `prometheus` or `prometheus-exporter` feature is enabled.
+#[cfg(any(feature = "prometheus", feature = "prometheus-exporter"))]
+pub struct AutometricsTracker {
+    start: Instant,
+    gauge_labels: Option<GaugeLabels>,
+}
+
+#[cfg(any(feature = "prometheus", feature = "prometheus-exporter"))]
+impl TrackMetrics for AutometricsTracker {
+    fn start(gauge_labels: Option<&GaugeLabels>) -> Self {
+        if let Some(labels) = gauge_labels {
+            let pairs = labels.to_pairs();
+            crate::registry::gauge()
+                .with_label_values(&pairs.iter().map(|(_, v)| v.as_str()).collect::<Vec<_>>())
+                .inc();
+
+            #[cfg(feature = "otlp-exporter")]
+            crate::otlp_exporter::record_gauge(1, &crate::otlp_exporter::key_values(&pairs));
+        }
+        Self {
+            start: Instant::now(),
+            gauge_labels: gauge_labels.map(|l| GaugeLabels {
+                function: l.function,
+                module: l.module,
+            }),
+        }
+    }
+
+    fn finish(self, counter_labels: &CounterLabels, histogram_labels: &HistogramLabels) {
+        if let Some(labels) = &self.gauge_labels {
+            let pairs = labels.to_pairs();
+            crate::registry::gauge()
+                .with_label_values(&pairs.iter().map(|(_, v)| v.as_str()).collect::<Vec<_>>())
+                .dec();
+
+            #[cfg(feature = "otlp-exporter")]
+            crate::otlp_exporter::record_gauge(-1, &crate::otlp_exporter::key_values(&pairs));
+        }
+
+        let duration = self.start.elapsed().as_secs_f64();
+
+        let counter_pairs = counter_labels.to_pairs();
+        crate::registry::counter()
+            .with_label_values(&counter_pairs.iter().map(|(_, v)| v.as_str()).collect::<Vec<_>>())
+            .inc();
+
+        #[cfg(feature = "otlp-exporter")]
+        crate::otlp_exporter::record_counter(&crate::otlp_exporter::key_values(&counter_pairs));
+
+        let histogram_pairs = histogram_labels.to_pairs();
+        let histogram = crate::registry::histogram()
+            .with_label_values(&histogram_pairs.iter().map(|(_, v)| v.as_str()).collect::<Vec<_>>());
+
+        #[cfg(feature = "otlp-exporter")]
+        crate::otlp_exporter::record_histogram(duration, &crate::otlp_exporter::key_values(&histogram_pairs));
+
+        #[cfg(feature = "exemplars")]
+        {
+            if crate::prometheus_exporter::exemplars_enabled() {
+                if let Some(exemplar) = crate::exemplars::current_trace_exemplar() {
+                    histogram.observe_with_exemplar(
+                        duration,
+                        prometheus::labels! { "trace_id" => exemplar.trace_id.as_str(), "span_id" => exemplar.span_id.as_str() },
+                    );
+                    return;
+                }
+            }
+        }
+
+        histogram.observe(duration);
+    }
+}